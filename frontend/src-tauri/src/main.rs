@@ -1,7 +1,221 @@
 // Prevents additional console window on Windows in release
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use tauri::Manager;
+// NOTE: this crate has no checked-in Cargo.toml in this tree, so the
+// dependencies below aren't declared anywhere. Whoever adds the manifest
+// needs: tauri (with the `system-tray` feature), webbrowser, url, serde
+// (with the `derive` feature), serde_json, reqwest (with the `json` and
+// `stream` features), futures-util, semver, sha2, and tokio (with the
+// `time` feature).
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{
+    CustomMenuItem, Manager, Menu, MenuItem, PhysicalPosition, PhysicalSize, Submenu, SystemTray,
+    SystemTrayEvent, SystemTrayMenu, SystemTrayMenuItem, WindowBuilder, WindowEvent,
+    WindowMenuEvent, WindowUrl,
+};
+
+const MIN_WINDOW_WIDTH: u32 = 1200;
+const MIN_WINDOW_HEIGHT: u32 = 700;
+const GEOMETRY_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Where to look for release manifests. Points at the project's own release
+/// feed; override by pointing this at a mirror if you're building a fork.
+const UPDATE_ENDPOINT: &str = "https://releases.agentverse.app/latest.json";
+
+/// Persisted window geometry, restored on the next launch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct WindowGeometry {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    maximized: bool,
+}
+
+fn window_state_path(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    let dir = app
+        .path_resolver()
+        .app_data_dir()
+        .ok_or_else(|| "could not resolve app data dir".to_string())?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join("window-state.json"))
+}
+
+fn save_window_geometry(app: &tauri::AppHandle, window: &tauri::Window) {
+    let Ok(path) = window_state_path(app) else {
+        return;
+    };
+    let maximized = window.is_maximized().unwrap_or(false);
+    // `inner_size` (not `outer_size`, which includes window decorations) to
+    // match `set_size`, which sets the inner size on restore.
+    let (Ok(position), Ok(size)) = (window.outer_position(), window.inner_size()) else {
+        return;
+    };
+    let geometry = WindowGeometry {
+        x: position.x,
+        y: position.y,
+        width: size.width.max(MIN_WINDOW_WIDTH),
+        height: size.height.max(MIN_WINDOW_HEIGHT),
+        maximized,
+    };
+    if let Ok(contents) = serde_json::to_string(&geometry) {
+        let _ = fs::write(path, contents);
+    }
+}
+
+/// Schedules a debounced geometry save: rapid Moved/Resized events while the
+/// user is dragging only result in a single write, 300ms after they stop.
+/// Runs as a single async task per event rather than an OS thread, since a
+/// drag can fire hundreds of events in quick succession.
+fn debounce_geometry_save(app: &tauri::AppHandle, window: &tauri::Window, generation: &Arc<AtomicU64>) {
+    let expected = generation.fetch_add(1, Ordering::SeqCst) + 1;
+    let app = app.clone();
+    let window = window.clone();
+    let generation = generation.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(GEOMETRY_DEBOUNCE).await;
+        if generation.load(Ordering::SeqCst) == expected {
+            save_window_geometry(&app, &window);
+        }
+    });
+}
+
+/// True if the two axis-aligned rectangles (given as `x, y, width, height`)
+/// overlap at all.
+fn rects_intersect(ax: i32, ay: i32, aw: u32, ah: u32, bx: i32, by: i32, bw: u32, bh: u32) -> bool {
+    let a_right = ax + aw as i32;
+    let a_bottom = ay + ah as i32;
+    let b_right = bx + bw as i32;
+    let b_bottom = by + bh as i32;
+    ax < b_right && a_right > bx && ay < b_bottom && a_bottom > by
+}
+
+/// Restores previously saved geometry, clamped to the minimum window size
+/// and validated against the currently available monitors so a window saved
+/// on a display that's no longer connected can't be restored off-screen.
+fn restore_window_geometry(app: &tauri::AppHandle, window: &tauri::Window) {
+    let Ok(path) = window_state_path(app) else {
+        return;
+    };
+    let Ok(contents) = fs::read_to_string(path) else {
+        return;
+    };
+    let Ok(mut geometry) = serde_json::from_str::<WindowGeometry>(&contents) else {
+        return;
+    };
+
+    geometry.width = geometry.width.max(MIN_WINDOW_WIDTH);
+    geometry.height = geometry.height.max(MIN_WINDOW_HEIGHT);
+
+    let fits_a_monitor = window.available_monitors().map(|monitors| {
+        monitors.iter().any(|monitor| {
+            let pos = monitor.position();
+            let size = monitor.size();
+            rects_intersect(
+                geometry.x,
+                geometry.y,
+                geometry.width,
+                geometry.height,
+                pos.x,
+                pos.y,
+                size.width,
+                size.height,
+            )
+        })
+    });
+
+    if fits_a_monitor != Ok(true) {
+        return;
+    }
+
+    let _ = window.set_position(tauri::Position::Physical(PhysicalPosition::new(
+        geometry.x,
+        geometry.y,
+    )));
+    let _ = window.set_size(tauri::Size::Physical(PhysicalSize::new(
+        geometry.width,
+        geometry.height,
+    )));
+    if geometry.maximized {
+        let _ = window.maximize();
+    }
+}
+
+/// What should happen when the user clicks the main window's close button.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum CloseBehavior {
+    /// Always ask the frontend to confirm before quitting.
+    Ask,
+    /// Quit the app outright.
+    Quit,
+    /// Hide the main window and keep running in the tray.
+    MinimizeToTray,
+}
+
+impl Default for CloseBehavior {
+    fn default() -> Self {
+        CloseBehavior::Ask
+    }
+}
+
+fn close_behavior_path(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    let dir = app
+        .path_resolver()
+        .app_config_dir()
+        .ok_or_else(|| "could not resolve app config dir".to_string())?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join("close-behavior.json"))
+}
+
+fn read_close_behavior(app: &tauri::AppHandle) -> CloseBehavior {
+    close_behavior_path(app)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Which URLs `open_external` is willing to hand to the OS.
+///
+/// `domains` is an allowlist of hosts; an empty list means "any host is fine
+/// as long as the scheme is allowed".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct UrlPolicy {
+    schemes: Vec<String>,
+    domains: Vec<String>,
+}
+
+impl Default for UrlPolicy {
+    fn default() -> Self {
+        UrlPolicy {
+            schemes: vec!["http".into(), "https".into(), "mailto".into()],
+            domains: Vec::new(),
+        }
+    }
+}
+
+fn url_policy_path(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    let dir = app
+        .path_resolver()
+        .app_config_dir()
+        .ok_or_else(|| "could not resolve app config dir".to_string())?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join("url-policy.json"))
+}
+
+fn read_url_policy(app: &tauri::AppHandle) -> UrlPolicy {
+    url_policy_path(app)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
 
 // Custom commands that can be called from the frontend
 #[tauri::command]
@@ -14,23 +228,580 @@ fn get_app_version() -> String {
     env!("CARGO_PKG_VERSION").to_string()
 }
 
+fn scheme_allowed(schemes: &[String], scheme: &str) -> bool {
+    schemes.iter().any(|allowed| allowed.eq_ignore_ascii_case(scheme))
+}
+
+/// True if `host` passes the domain allowlist. An empty allowlist permits
+/// any host, and schemes like `mailto:`/`tel:` have no host to check at all.
+fn host_allowed(domains: &[String], host: Option<&str>) -> bool {
+    if domains.is_empty() {
+        return true;
+    }
+    match host {
+        Some(host) => domains.iter().any(|domain| domain == host),
+        None => true,
+    }
+}
+
+#[tauri::command]
+fn open_external(app: tauri::AppHandle, url: &str) -> Result<(), String> {
+    let parsed = url::Url::parse(url).map_err(|e| format!("invalid URL: {}", e))?;
+    let policy = read_url_policy(&app);
+
+    if !scheme_allowed(&policy.schemes, parsed.scheme()) {
+        return Err(format!("scheme \"{}\" is not allowed", parsed.scheme()));
+    }
+
+    if !host_allowed(&policy.domains, parsed.host_str()) {
+        return Err(format!(
+            "host \"{}\" is not in the allowlist",
+            parsed.host_str().unwrap_or("")
+        ));
+    }
+
+    webbrowser::open(url).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_url_policy(app: tauri::AppHandle) -> UrlPolicy {
+    read_url_policy(&app)
+}
+
+#[tauri::command]
+fn set_url_policy(app: tauri::AppHandle, schemes: Vec<String>, domains: Vec<String>) -> Result<(), String> {
+    let path = url_policy_path(&app)?;
+    let policy = UrlPolicy { schemes, domains };
+    let contents = serde_json::to_string(&policy).map_err(|e| e.to_string())?;
+    fs::write(path, contents).map_err(|e| e.to_string())
+}
+
+/// A release manifest as published at [`UPDATE_ENDPOINT`].
+#[derive(Debug, Clone, Deserialize)]
+struct UpdateManifest {
+    version: String,
+    notes: String,
+    url: String,
+    /// Lowercase hex-encoded SHA-256 of the installer at `url`, checked
+    /// before the installer is ever executed.
+    sha256: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct UpdateInfo {
+    version: String,
+    notes: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct DownloadProgress {
+    bytes: u64,
+    total: u64,
+}
+
+async fn fetch_manifest() -> Result<UpdateManifest, String> {
+    reqwest::get(UPDATE_ENDPOINT)
+        .await
+        .map_err(|e| e.to_string())?
+        .json::<UpdateManifest>()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// True if `remote` is a strictly newer semver than `current`. Falls back to
+/// a plain string inequality if either side isn't valid semver, so malformed
+/// version strings don't silently hide a pending update.
+fn is_newer_version(remote: &str, current: &str) -> bool {
+    match (semver::Version::parse(remote), semver::Version::parse(current)) {
+        (Ok(remote), Ok(current)) => remote > current,
+        _ => remote != current,
+    }
+}
+
+/// Checks the release feed for a newer version than the one currently
+/// running, emitting `update-available`/`update-error` so the UI can show a
+/// non-blocking banner without having to poll.
+#[tauri::command]
+async fn check_for_update(app: tauri::AppHandle) -> Result<Option<UpdateInfo>, String> {
+    match fetch_manifest().await {
+        Ok(manifest) if is_newer_version(&manifest.version, &get_app_version()) => {
+            let info = UpdateInfo {
+                version: manifest.version,
+                notes: manifest.notes,
+            };
+            let _ = app.emit_all("update-available", &info);
+            Ok(Some(info))
+        }
+        Ok(_) => Ok(None),
+        Err(err) => {
+            let _ = app.emit_all("update-error", &err);
+            Err(err)
+        }
+    }
+}
+
+/// Name of the downloaded installer on disk, platform-appropriate so the OS
+/// knows how to run it.
+fn installer_file_name() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "agentverse-update-installer.exe"
+    } else {
+        "agentverse-update-installer"
+    }
+}
+
+/// Launches the downloaded installer. The installer is responsible for
+/// replacing the application files and relaunching once this process exits.
+fn launch_installer(path: &std::path::Path) -> Result<(), String> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut permissions = fs::metadata(path).map_err(|e| e.to_string())?.permissions();
+        permissions.set_mode(0o755);
+        fs::set_permissions(path, permissions).map_err(|e| e.to_string())?;
+    }
+
+    std::process::Command::new(path)
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// True if a downloaded installer's digest matches the checksum published
+/// in the release manifest (compared case-insensitively).
+fn checksum_matches(digest: &[u8], expected_hex: &str) -> bool {
+    to_hex(digest).eq_ignore_ascii_case(expected_hex)
+}
+
+/// Downloads the installer to a temp file, streaming both the write and the
+/// SHA-256 hash so the whole payload is never buffered in memory, then
+/// verifies it against the manifest's checksum before returning the path.
+/// A compromised release host or a TLS MITM can't get arbitrary code run
+/// this way without also forging the checksum.
+async fn download_and_verify_installer(
+    app: &tauri::AppHandle,
+    manifest: &UpdateManifest,
+) -> Result<std::path::PathBuf, String> {
+    use sha2::{Digest, Sha256};
+    use std::io::Write;
+
+    let response = reqwest::get(&manifest.url).await.map_err(|e| e.to_string())?;
+    let total = response.content_length().unwrap_or(0);
+    let mut downloaded: u64 = 0;
+    let mut stream = response.bytes_stream();
+
+    let installer_path = std::env::temp_dir().join(installer_file_name());
+    let mut file = fs::File::create(&installer_path).map_err(|e| e.to_string())?;
+    let mut hasher = Sha256::new();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| e.to_string())?;
+        downloaded += chunk.len() as u64;
+        hasher.update(&chunk);
+        file.write_all(&chunk).map_err(|e| e.to_string())?;
+        let _ = app.emit_all(
+            "download-progress",
+            DownloadProgress {
+                bytes: downloaded,
+                total,
+            },
+        );
+    }
+    drop(file);
+
+    if !checksum_matches(&hasher.finalize(), &manifest.sha256) {
+        let _ = fs::remove_file(&installer_path);
+        return Err("downloaded installer failed checksum verification".to_string());
+    }
+
+    Ok(installer_path)
+}
+
+/// Downloads and verifies the latest installer, streaming `download-progress`
+/// events as it goes, then launches the installer and exits the app. This
+/// command does not relaunch the app itself; that is left to the installer.
 #[tauri::command]
-fn open_external(url: &str) {
-    let _ = webbrowser::open(url);
+async fn download_and_install_update(app: tauri::AppHandle) -> Result<(), String> {
+    let manifest = fetch_manifest().await.map_err(|err| {
+        let _ = app.emit_all("update-error", &err);
+        err
+    })?;
+
+    let installer_path = download_and_verify_installer(&app, &manifest)
+        .await
+        .map_err(|err| {
+            let _ = app.emit_all("update-error", &err);
+            err
+        })?;
+
+    launch_installer(&installer_path)?;
+
+    // The installer takes over from here: it replaces the application files
+    // and relaunches the app once this process has exited.
+    app.exit(0);
+    Ok(())
+}
+
+#[tauri::command]
+fn get_current_version() -> String {
+    get_app_version()
+}
+
+#[tauri::command]
+fn set_menu_item_enabled(window: tauri::Window, id: String, enabled: bool) -> Result<(), String> {
+    window
+        .menu_handle()
+        .get_item(&id)
+        .set_enabled(enabled)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_close_behavior(app: tauri::AppHandle) -> CloseBehavior {
+    read_close_behavior(&app)
+}
+
+#[tauri::command]
+fn set_close_behavior(app: tauri::AppHandle, mode: CloseBehavior) -> Result<(), String> {
+    let path = close_behavior_path(&app)?;
+    let contents = serde_json::to_string(&mode).map_err(|e| e.to_string())?;
+    fs::write(path, contents).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn confirm_quit(app: tauri::AppHandle) {
+    app.exit(0);
+}
+
+#[tauri::command]
+fn hide_to_tray(window: tauri::Window) -> Result<(), String> {
+    window.hide().map_err(|e| e.to_string())
+}
+
+/// Opens a new isolated window for an agent, or focuses it if a window with
+/// the same `label` already exists.
+///
+/// This is `async` so the existing-window lookup and the window build happen
+/// off the main thread's call stack: doing both synchronously back-to-back
+/// has been observed to overflow the main thread stack on Windows.
+#[tauri::command]
+async fn open_agent_window(
+    app: tauri::AppHandle,
+    label: String,
+    url: String,
+    title: String,
+) -> Result<String, String> {
+    if let Some(existing) = app.get_window(&label) {
+        existing.set_focus().map_err(|e| e.to_string())?;
+        return Ok(label);
+    }
+
+    let window_url = match url::Url::parse(&url) {
+        Ok(parsed) => WindowUrl::External(parsed),
+        Err(_) => WindowUrl::App(url.into()),
+    };
+
+    WindowBuilder::new(&app, label.clone(), window_url)
+        .title(title)
+        .min_inner_size(MIN_WINDOW_WIDTH as f64, MIN_WINDOW_HEIGHT as f64)
+        .center()
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    Ok(label)
+}
+
+#[tauri::command]
+fn reset_window_state(app: tauri::AppHandle) -> Result<(), String> {
+    let path = window_state_path(&app)?;
+    if path.exists() {
+        fs::remove_file(path).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+fn build_menu() -> Menu {
+    let file_menu = Submenu::new(
+        "File",
+        Menu::new()
+            .add_item(CustomMenuItem::new("new-agent", "New Agent").accelerator("CmdOrCtrl+N"))
+            .add_item(CustomMenuItem::new("open-agent", "Open Agent..."))
+            .add_native_item(MenuItem::Separator)
+            .add_item(CustomMenuItem::new("quit", "Quit").accelerator("CmdOrCtrl+Q")),
+    );
+
+    let edit_menu = Submenu::new(
+        "Edit",
+        Menu::new()
+            .add_native_item(MenuItem::Undo)
+            .add_native_item(MenuItem::Redo)
+            .add_native_item(MenuItem::Separator)
+            .add_native_item(MenuItem::Cut)
+            .add_native_item(MenuItem::Copy)
+            .add_native_item(MenuItem::Paste)
+            .add_native_item(MenuItem::SelectAll),
+    );
+
+    let view_menu = Submenu::new(
+        "View",
+        Menu::new()
+            .add_item(CustomMenuItem::new("toggle-sidebar", "Toggle Sidebar"))
+            .add_item(CustomMenuItem::new("toggle-logs", "Toggle Logs Panel"))
+            .add_native_item(MenuItem::Separator)
+            .add_native_item(MenuItem::EnterFullScreen),
+    );
+
+    let agents_menu = Submenu::new(
+        "Agents",
+        Menu::new()
+            .add_item(CustomMenuItem::new("new-agent-from-menu", "New Agent"))
+            .add_item(CustomMenuItem::new("run-agent", "Run Selected Agent"))
+            .add_item(CustomMenuItem::new("stop-agent", "Stop Selected Agent")),
+    );
+
+    Menu::new()
+        .add_submenu(file_menu)
+        .add_submenu(edit_menu)
+        .add_submenu(view_menu)
+        .add_submenu(agents_menu)
+}
+
+fn build_tray() -> SystemTray {
+    let tray_menu = SystemTrayMenu::new()
+        .add_item(CustomMenuItem::new("tray-show", "Show"))
+        .add_item(CustomMenuItem::new("tray-hide", "Hide"))
+        .add_native_item(SystemTrayMenuItem::Separator)
+        .add_item(CustomMenuItem::new("tray-quit", "Quit"));
+
+    SystemTray::new().with_menu(tray_menu)
+}
+
+/// Applies the same quit/minimize/ask decision as the main window's
+/// `CloseRequested` handler, so File→Quit and the tray's Quit item can't
+/// bypass the "don't lose in-flight agent runs" protection that exists for
+/// exactly this case.
+fn request_quit(app: &tauri::AppHandle) {
+    match read_close_behavior(app) {
+        CloseBehavior::Quit => app.exit(0),
+        CloseBehavior::MinimizeToTray => {
+            if let Some(window) = app.get_window("main") {
+                let _ = window.hide();
+            }
+        }
+        CloseBehavior::Ask => {
+            if let Some(window) = app.get_window("main") {
+                let _ = window.emit("close-requested", ());
+            }
+        }
+    }
+}
+
+fn on_menu_event(event: WindowMenuEvent) {
+    let window = event.window();
+    match event.menu_item_id() {
+        "quit" => request_quit(&window.app_handle()),
+        id => {
+            let _ = window.emit(&format!("menu://{}", id), ());
+        }
+    }
+}
+
+fn on_system_tray_event(app: &tauri::AppHandle, event: SystemTrayEvent) {
+    if let SystemTrayEvent::MenuItemClick { id, .. } = event {
+        match id.as_str() {
+            "tray-show" => {
+                if let Some(window) = app.get_window("main") {
+                    let _ = window.show();
+                    let _ = window.set_focus();
+                }
+            }
+            "tray-hide" => {
+                if let Some(window) = app.get_window("main") {
+                    let _ = window.hide();
+                }
+            }
+            "tray-quit" => request_quit(app),
+            _ => {}
+        }
+    }
 }
 
 fn main() {
     tauri::Builder::default()
-        .invoke_handler(tauri::generate_handler![greet, get_app_version, open_external])
+        .invoke_handler(tauri::generate_handler![
+            greet,
+            get_app_version,
+            open_external,
+            get_url_policy,
+            set_url_policy,
+            set_menu_item_enabled,
+            get_close_behavior,
+            set_close_behavior,
+            confirm_quit,
+            hide_to_tray,
+            open_agent_window,
+            reset_window_state,
+            check_for_update,
+            download_and_install_update,
+            get_current_version
+        ])
+        .menu(build_menu())
+        .on_menu_event(on_menu_event)
+        .system_tray(build_tray())
+        .on_system_tray_event(on_system_tray_event)
         .setup(|app| {
             // Setup window
             let window = app.get_window("main").unwrap();
-            
+
             // Set minimum size
-            let _ = window.set_min_size(Some(tauri::LogicalSize::new(1200, 700)));
-            
+            let _ = window.set_min_size(Some(tauri::LogicalSize::new(
+                MIN_WINDOW_WIDTH,
+                MIN_WINDOW_HEIGHT,
+            )));
+
+            // Restore the last saved position/size before the window is shown,
+            // so the user never sees it jump after appearing at the default spot.
+            restore_window_geometry(&app.handle(), &window);
+
+            // Let the frontend decide whether to actually close, unless the
+            // user has already told us to just quit or minimize to tray.
+            let app_handle = app.handle();
+            let geometry_generation = Arc::new(AtomicU64::new(0));
+            window.on_window_event(move |event| match event {
+                WindowEvent::CloseRequested { api, .. } => {
+                    match read_close_behavior(&app_handle) {
+                        CloseBehavior::Quit => {}
+                        CloseBehavior::MinimizeToTray => {
+                            api.prevent_close();
+                            if let Some(window) = app_handle.get_window("main") {
+                                let _ = window.hide();
+                            }
+                        }
+                        CloseBehavior::Ask => {
+                            api.prevent_close();
+                            if let Some(window) = app_handle.get_window("main") {
+                                let _ = window.emit("close-requested", ());
+                            }
+                        }
+                    }
+                }
+                WindowEvent::Moved(_) | WindowEvent::Resized(_) => {
+                    if let Some(window) = app_handle.get_window("main") {
+                        debounce_geometry_save(&app_handle, &window, &geometry_generation);
+                    }
+                }
+                _ => {}
+            });
+
+            // Check for a new release in the background so startup isn't
+            // blocked on a network round-trip.
+            let update_check_handle = app.handle();
+            tauri::async_runtime::spawn(async move {
+                let _ = check_for_update(update_check_handle).await;
+            });
+
             Ok(())
         })
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rects_intersect_when_window_is_on_monitor() {
+        assert!(rects_intersect(100, 100, 1200, 700, 0, 0, 1920, 1080));
+    }
+
+    #[test]
+    fn rects_intersect_false_when_window_is_off_screen() {
+        assert!(!rects_intersect(5000, 5000, 1200, 700, 0, 0, 1920, 1080));
+    }
+
+    #[test]
+    fn rects_intersect_false_when_only_touching_edges() {
+        // A window positioned exactly at the monitor's right edge doesn't
+        // actually overlap any visible pixels.
+        assert!(!rects_intersect(1920, 0, 1200, 700, 0, 0, 1920, 1080));
+    }
+
+    #[test]
+    fn rects_intersect_true_when_partially_overlapping() {
+        assert!(rects_intersect(1800, 900, 1200, 700, 0, 0, 1920, 1080));
+    }
+
+    fn default_schemes() -> Vec<String> {
+        vec!["http".into(), "https".into(), "mailto".into()]
+    }
+
+    #[test]
+    fn scheme_allowed_is_case_insensitive() {
+        assert!(scheme_allowed(&default_schemes(), "HTTPS"));
+    }
+
+    #[test]
+    fn scheme_allowed_rejects_unlisted_scheme() {
+        assert!(!scheme_allowed(&default_schemes(), "file"));
+    }
+
+    #[test]
+    fn host_allowed_with_empty_allowlist_permits_any_host() {
+        assert!(host_allowed(&[], Some("example.com")));
+        assert!(host_allowed(&[], None));
+    }
+
+    #[test]
+    fn host_allowed_checks_hostname_against_allowlist() {
+        let domains = vec!["example.com".to_string()];
+        assert!(host_allowed(&domains, Some("example.com")));
+        assert!(!host_allowed(&domains, Some("evil.example.net")));
+    }
+
+    #[test]
+    fn host_allowed_does_not_block_hostless_schemes_like_mailto() {
+        // A non-empty domain allowlist must not block mailto:/tel: links,
+        // which have no host at all.
+        let domains = vec!["example.com".to_string()];
+        assert!(host_allowed(&domains, None));
+    }
+
+    #[test]
+    fn is_newer_version_detects_a_semver_upgrade() {
+        assert!(is_newer_version("1.2.0", "1.1.0"));
+    }
+
+    #[test]
+    fn is_newer_version_rejects_a_semver_downgrade() {
+        assert!(!is_newer_version("1.0.0", "1.2.0"));
+    }
+
+    #[test]
+    fn is_newer_version_rejects_the_same_semver() {
+        assert!(!is_newer_version("1.2.0", "1.2.0"));
+    }
+
+    #[test]
+    fn is_newer_version_falls_back_to_string_inequality_for_non_semver() {
+        assert!(is_newer_version("build-42", "build-41"));
+        assert!(!is_newer_version("build-41", "build-41"));
+    }
+
+    #[test]
+    fn checksum_matches_is_case_insensitive() {
+        let digest = [0xabu8, 0xcd, 0xef];
+        assert!(checksum_matches(&digest, "ABCDEF"));
+        assert!(checksum_matches(&digest, "abcdef"));
+    }
+
+    #[test]
+    fn checksum_matches_rejects_a_mismatched_digest() {
+        let digest = [0xabu8, 0xcd, 0xef];
+        assert!(!checksum_matches(&digest, "000000"));
+    }
 }
\ No newline at end of file